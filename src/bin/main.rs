@@ -1,8 +1,14 @@
+#[cfg(feature = "std")]
 use clap::Parser;
-use mnist::{Mnist, plot};
+#[cfg(feature = "std")]
+use mnist::Mnist;
+#[cfg(feature = "plotting")]
+use mnist::plot;
+#[cfg(feature = "std")]
 use std::path::PathBuf;
 
 /// A demo application to showcase the mnist-parser library.
+#[cfg(feature = "std")]
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 struct Args {
@@ -24,6 +30,7 @@ struct Args {
     plot: bool,
 }
 
+#[cfg(feature = "std")]
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
 
@@ -83,3 +90,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("\nDemo finished successfully!");
     Ok(())
 }
+
+/// This demo loads files via [`Mnist::load`], which requires the "std" feature.
+#[cfg(not(feature = "std"))]
+fn main() {
+    eprintln!("This demo requires the \"std\" feature; rebuild with `--features std`.");
+}
@@ -0,0 +1,239 @@
+//! Configurable loading of MNIST-family datasets.
+
+use crate::error::MnistError;
+use crate::{num_classes, read_images, read_labels, Image, Mnist};
+use std::path::{Path, PathBuf};
+
+/// Pixel normalization strategy applied on top of the raw `u8` pixel data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Normalize {
+    /// Leave pixel values as raw `u8` (0-255).
+    #[default]
+    Raw,
+    /// Scale pixel values to `[0.0, 1.0]`.
+    Unit,
+    /// Standardize pixel values to zero mean, unit variance.
+    Standardize,
+}
+
+/// Label encoding strategy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LabelEncoding {
+    /// Labels as raw `u8` class indices (0-9).
+    #[default]
+    Scalar,
+    /// Labels as one-hot `f32` vectors, [`Mnist::nclasses`](crate::Mnist::nclasses) wide.
+    OneHot,
+}
+
+/// Which dataset variant to load; determines the expected file names in `dir`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Variant {
+    /// The original handwritten-digit MNIST dataset.
+    #[default]
+    Digits,
+    /// Fashion-MNIST: same IDX layout, clothing images instead of digits.
+    Fashion,
+}
+
+impl Variant {
+    /// Returns `(train_images, train_labels, test_images, test_labels)` file names.
+    fn filenames(&self) -> (&'static str, &'static str, &'static str, &'static str) {
+        match self {
+            Variant::Digits => (
+                "train-images-idx3-ubyte",
+                "train-labels-idx1-ubyte",
+                "t10k-images-idx3-ubyte",
+                "t10k-labels-idx1-ubyte",
+            ),
+            // Fashion-MNIST ships under the same file names as the digits set, meant
+            // to be a drop-in replacement in its own directory.
+            Variant::Fashion => (
+                "train-images-idx3-ubyte",
+                "train-labels-idx1-ubyte",
+                "t10k-images-idx3-ubyte",
+                "t10k-labels-idx1-ubyte",
+            ),
+        }
+    }
+}
+
+/// Builds an [`Mnist`] dataset with configurable normalization, label encoding,
+/// dataset variant, and a train/validation split.
+///
+/// `Mnist::load(dir)` is shorthand for `MnistBuilder::new(dir).build()`.
+pub struct MnistBuilder {
+    dir: PathBuf,
+    variant: Variant,
+    normalize: Normalize,
+    label_encoding: LabelEncoding,
+    validation_split: usize,
+}
+
+impl MnistBuilder {
+    /// Creates a builder that loads the default [`Variant::Digits`] dataset from `dir`.
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+            variant: Variant::default(),
+            normalize: Normalize::default(),
+            label_encoding: LabelEncoding::default(),
+            validation_split: 0,
+        }
+    }
+
+    /// Sets the dataset variant, which determines the expected file names.
+    pub fn variant(mut self, variant: Variant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    /// Sets the pixel normalization strategy recorded on the built [`Mnist`].
+    pub fn normalize(mut self, normalize: Normalize) -> Self {
+        self.normalize = normalize;
+        self
+    }
+
+    /// Sets the label encoding recorded on the built [`Mnist`].
+    pub fn label_encoding(mut self, label_encoding: LabelEncoding) -> Self {
+        self.label_encoding = label_encoding;
+        self
+    }
+
+    /// Carves the trailing `n` training examples into `val_images`/`val_labels`.
+    pub fn validation_split(mut self, n: usize) -> Self {
+        self.validation_split = n;
+        self
+    }
+
+    /// Loads the dataset files from `dir` and applies the configured split.
+    pub fn build(self) -> Result<Mnist, MnistError> {
+        let (train_images_name, train_labels_name, test_images_name, test_labels_name) =
+            self.variant.filenames();
+
+        let mut train_labels = read_labels(self.dir.join(train_labels_name))?;
+        let mut train_images = read_images(self.dir.join(train_images_name))?;
+
+        let test_labels = read_labels(self.dir.join(test_labels_name))?;
+        let test_images = read_images(self.dir.join(test_images_name))?;
+
+        if train_labels.len() != train_images.len() {
+            return Err(MnistError::LabelImageCountMismatch {
+                images: train_images.len(),
+                labels: train_labels.len(),
+            });
+        }
+        if test_labels.len() != test_images.len() {
+            return Err(MnistError::LabelImageCountMismatch {
+                images: test_images.len(),
+                labels: test_labels.len(),
+            });
+        }
+
+        if let (Some(train), Some(test)) = (train_images.first(), test_images.first()) {
+            if (train.width(), train.height()) != (test.width(), test.height()) {
+                return Err(MnistError::InvalidImageDimensions {
+                    expected: (train.width() as u32, train.height() as u32),
+                    found: (test.width() as u32, test.height() as u32),
+                });
+            }
+        }
+
+        let split_at = train_images.len().saturating_sub(self.validation_split);
+        let val_images: Vec<Image> = train_images.split_off(split_at);
+        let val_labels: Vec<u8> = train_labels.split_off(split_at);
+
+        let nclasses = num_classes(&train_labels)
+            .max(num_classes(&val_labels))
+            .max(num_classes(&test_labels));
+
+        Ok(Mnist {
+            train_images,
+            train_labels,
+            val_images,
+            val_labels,
+            test_images,
+            test_labels,
+            normalize: self.normalize,
+            label_encoding: self.label_encoding,
+            nclasses,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn write_idx_images(path: &Path, num_images: u32, rows: u32, cols: u32, fill: u8) {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&2051u32.to_be_bytes());
+        bytes.extend_from_slice(&num_images.to_be_bytes());
+        bytes.extend_from_slice(&rows.to_be_bytes());
+        bytes.extend_from_slice(&cols.to_be_bytes());
+        let pixel_bytes = num_images as usize * rows as usize * cols as usize;
+        bytes.resize(bytes.len() + pixel_bytes, fill);
+        fs::write(path, bytes).unwrap();
+    }
+
+    fn write_idx_labels(path: &Path, labels: &[u8]) {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&2049u32.to_be_bytes());
+        bytes.extend_from_slice(&(labels.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(labels);
+        fs::write(path, bytes).unwrap();
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("mnist-rs-builder-test-{name}-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn fashion_and_digits_share_filenames() {
+        assert_eq!(Variant::Digits.filenames(), Variant::Fashion.filenames());
+    }
+
+    #[test]
+    fn build_splits_validation_and_derives_nclasses() {
+        let dir = temp_dir("ok");
+        write_idx_images(&dir.join("train-images-idx3-ubyte"), 5, 2, 2, 1);
+        write_idx_labels(&dir.join("train-labels-idx1-ubyte"), &[0, 1, 2, 3, 4]);
+        write_idx_images(&dir.join("t10k-images-idx3-ubyte"), 2, 2, 2, 1);
+        write_idx_labels(&dir.join("t10k-labels-idx1-ubyte"), &[0, 1]);
+
+        let mnist = MnistBuilder::new(&dir).validation_split(2).build().unwrap();
+
+        assert_eq!(mnist.train_images.len(), 3);
+        assert_eq!(mnist.train_labels.len(), 3);
+        assert_eq!(mnist.val_images.len(), 2);
+        assert_eq!(mnist.val_labels.len(), 2);
+        assert_eq!(mnist.test_images.len(), 2);
+        assert_eq!(mnist.nclasses, 5);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn build_rejects_label_image_count_mismatch() {
+        let dir = temp_dir("mismatch");
+        write_idx_images(&dir.join("train-images-idx3-ubyte"), 5, 2, 2, 1);
+        write_idx_labels(&dir.join("train-labels-idx1-ubyte"), &[0, 1, 2, 3]);
+        write_idx_images(&dir.join("t10k-images-idx3-ubyte"), 2, 2, 2, 1);
+        write_idx_labels(&dir.join("t10k-labels-idx1-ubyte"), &[0, 1]);
+
+        match MnistBuilder::new(&dir).build() {
+            Err(MnistError::LabelImageCountMismatch {
+                images: 5,
+                labels: 4,
+            }) => {}
+            Ok(_) => panic!("expected a LabelImageCountMismatch error"),
+            Err(_) => panic!("expected a LabelImageCountMismatch error"),
+        }
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,133 @@
+//! Optional auto-download support for the MNIST dataset files.
+//!
+//! Only compiled in if the "download" feature is enabled.
+
+use crate::error::MnistError;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Read};
+use std::path::Path;
+
+/// Default mirror hosting the canonical, gzip-compressed MNIST files.
+pub const DEFAULT_BASE_URL: &str = "https://storage.googleapis.com/cvdf-datasets/mnist/";
+
+/// `(decompressed file name, remote file name)` pairs for the four canonical,
+/// gzip-compressed MNIST digits files, as hosted at [`DEFAULT_BASE_URL`].
+pub const DEFAULT_FILENAMES: [(&str, &str); 4] = [
+    ("train-images-idx3-ubyte", "train-images-idx3-ubyte.gz"),
+    ("train-labels-idx1-ubyte", "train-labels-idx1-ubyte.gz"),
+    ("t10k-images-idx3-ubyte", "t10k-images-idx3-ubyte.gz"),
+    ("t10k-labels-idx1-ubyte", "t10k-labels-idx1-ubyte.gz"),
+];
+
+/// Downloads and gunzips any of `filenames` that are missing from `dir`.
+///
+/// `filenames` is a list of `(decompressed file name, remote file name)` pairs,
+/// fetched from `{base_url}{remote file name}`; pass [`DEFAULT_FILENAMES`] for the
+/// canonical MNIST digits files, or a different list to point at a mirror with a
+/// different layout (e.g. Fashion-MNIST's files, which share the digits' names).
+///
+/// Files already present in `dir` are left untouched, so this is safe to call
+/// before every [`crate::Mnist::load`].
+pub fn download_missing(
+    dir: &Path,
+    base_url: &str,
+    filenames: &[(&str, &str)],
+) -> Result<(), MnistError> {
+    std::fs::create_dir_all(dir)?;
+
+    for (target_name, remote_name) in filenames {
+        let target_path = dir.join(target_name);
+        if target_path.exists() {
+            continue;
+        }
+        fetch_to_file(&format!("{base_url}{remote_name}"), remote_name, &target_path)?;
+    }
+
+    Ok(())
+}
+
+/// Fetches `url` and writes its (optionally gzip-compressed) body to `target_path`.
+///
+/// The body is written to a temporary file alongside `target_path` and only renamed
+/// into place once the copy succeeds, so a failed GET or a gzip stream that is cut
+/// short never leaves a truncated file at `target_path` for `download_missing`'s
+/// `exists()` check to mistake for a complete download.
+fn fetch_to_file(url: &str, remote_name: &str, target_path: &Path) -> Result<(), MnistError> {
+    let response = ureq::get(url)
+        .call()
+        .map_err(|e| MnistError::Download(format!("GET {url} failed: {e}")))?;
+
+    let body: Box<dyn Read> = Box::new(response.into_reader());
+    let mut reader = BufReader::new(body);
+
+    let tmp_path = target_path.with_extension("part");
+    let mut writer = BufWriter::new(File::create(&tmp_path)?);
+
+    if looks_like_gzip(remote_name, &mut reader)? {
+        let mut decoder = flate2::read::GzDecoder::new(reader);
+        io::copy(&mut decoder, &mut writer)?;
+    } else {
+        io::copy(&mut reader, &mut writer)?;
+    }
+    drop(writer);
+
+    std::fs::rename(&tmp_path, target_path)?;
+    Ok(())
+}
+
+/// Detects a gzip payload by the `.gz` extension or, failing that, by peeking
+/// at the stream's magic header (`1f 8b`) without consuming it.
+fn looks_like_gzip(remote_name: &str, reader: &mut BufReader<Box<dyn Read>>) -> io::Result<bool> {
+    if remote_name.ends_with(".gz") {
+        return Ok(true);
+    }
+    Ok(reader.fill_buf()?.starts_with(&[0x1f, 0x8b]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn reader_over(bytes: &'static [u8]) -> BufReader<Box<dyn Read>> {
+        BufReader::new(Box::new(bytes) as Box<dyn Read>)
+    }
+
+    #[test]
+    fn detects_gzip_by_extension() {
+        let mut reader = reader_over(b"does not matter");
+        assert!(looks_like_gzip("train-images-idx3-ubyte.gz", &mut reader).unwrap());
+    }
+
+    #[test]
+    fn detects_gzip_by_magic_bytes() {
+        let mut reader = reader_over(&[0x1f, 0x8b, 0x08, 0x00]);
+        assert!(looks_like_gzip("train-images-idx3-ubyte", &mut reader).unwrap());
+    }
+
+    #[test]
+    fn plain_file_is_not_gzip() {
+        let mut reader = reader_over(b"plain idx bytes");
+        assert!(!looks_like_gzip("train-images-idx3-ubyte", &mut reader).unwrap());
+    }
+
+    #[test]
+    fn download_missing_skips_existing_files_without_touching_them() {
+        let dir = std::env::temp_dir().join(format!(
+            "mnist-rs-download-test-skip-{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("already-here"), b"keep me").unwrap();
+
+        download_missing(
+            &dir,
+            "http://127.0.0.1:0/unreachable/",
+            &[("already-here", "already-here.gz")],
+        )
+        .unwrap();
+
+        assert_eq!(fs::read(dir.join("already-here")).unwrap(), b"keep me");
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -1,27 +1,61 @@
 use crate::fmt;
+#[cfg(feature = "std")]
 use std::error::Error;
+#[cfg(feature = "std")]
 use std::io;
 
 #[derive(Debug)]
 pub enum MnistError {
     /// An error occurred during file I/O (e.g., file not found, permission denied).
-    /// This wraps the underlying `std::io::Error`.
+    /// This wraps the underlying `std::io::Error`. Only available with the "std" feature.
+    #[cfg(feature = "std")]
     Io(io::Error),
 
+    /// Fewer bytes were available than the IDX header declared, e.g. a truncated
+    /// file or a download that was cut short. Distinguished from [`MnistError::Io`]
+    /// so callers can tell "corrupt/short data" from a generic I/O failure.
+    UnexpectedEof { expected: usize, found: usize },
+
     /// The file's magic number was incorrect, indicating a corrupt or wrong file type.
     InvalidMagicNumber { expected: u32, found: u32 },
 
-    /// The image dimensions in the file header do not match the expected 28x28.
+    /// The train and test image files in a dataset have mismatched dimensions.
     InvalidImageDimensions {
         expected: (u32, u32),
         found: (u32, u32),
     },
+
+    /// A dataset's image and label files have a different number of entries.
+    LabelImageCountMismatch { images: usize, labels: usize },
+
+    /// Fetching a dataset file from a remote URL failed.
+    ///
+    /// Only constructed when the "download" feature is enabled.
+    #[cfg(feature = "download")]
+    Download(String),
+
+    /// Encoding or writing a PNG file failed.
+    ///
+    /// Only constructed when the "png" feature is enabled.
+    #[cfg(feature = "png")]
+    Png(String),
+
+    /// A requested range extended past the number of available items.
+    ///
+    /// Only constructed when the "png" feature is enabled.
+    #[cfg(feature = "png")]
+    RangeOutOfBounds { len: usize, end: usize },
 }
 
 impl fmt::Display for MnistError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            #[cfg(feature = "std")]
             MnistError::Io(e) => write!(f, "I/O error: {e}"),
+            MnistError::UnexpectedEof { expected, found } => write!(
+                f,
+                "Unexpected end of data. Expected {expected} bytes, but found {found}"
+            ),
             MnistError::InvalidMagicNumber { expected, found } => write!(
                 f,
                 "Invalid magic number. Expected {expected}, but found {found}"
@@ -31,10 +65,24 @@ impl fmt::Display for MnistError {
                 "Invalid image dimensions. Expected {}x{}, but found {}x{}",
                 expected.0, expected.1, found.0, found.1
             ),
+            MnistError::LabelImageCountMismatch { images, labels } => write!(
+                f,
+                "Label and image files disagree on item count: {images} images, {labels} labels"
+            ),
+            #[cfg(feature = "download")]
+            MnistError::Download(msg) => write!(f, "Failed to download dataset file: {msg}"),
+            #[cfg(feature = "png")]
+            MnistError::Png(msg) => write!(f, "Failed to write PNG file: {msg}"),
+            #[cfg(feature = "png")]
+            MnistError::RangeOutOfBounds { len, end } => write!(
+                f,
+                "Range extends to {end}, but only {len} items are available"
+            ),
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl Error for MnistError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
@@ -44,6 +92,7 @@ impl Error for MnistError {
     }
 }
 
+#[cfg(feature = "std")]
 impl From<io::Error> for MnistError {
     fn from(err: io::Error) -> Self {
         MnistError::Io(err)
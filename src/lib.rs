@@ -1,37 +1,75 @@
 //! A simple crate for parsing the MNIST dataset.
 //!
 //! Provides utilities for loading image and label files, and includes
-//! an optional feature for plotting images with gnuplot.
+//! an optional feature for plotting images with gnuplot, and an optional
+//! feature for auto-downloading and decompressing the dataset files, and an
+//! optional feature for exporting the dataset as `ndarray` arrays.
+//!
+//! The core IDX parser in [`parse`] only needs a [`parse::ByteReader`], so it runs
+//! without `std` (e.g. over a memory-mapped or statically embedded buffer via
+//! [`parse::SliceReader`]) — the crate itself is `#![no_std]` unless the "std"
+//! feature is enabled. File- and path-based conveniences — including [`Mnist`]
+//! itself — require the "std" feature.
+
+#![cfg_attr(not(feature = "std"), no_std)]
 
 // Only bring in gnuplot if the "plotting" feature is enabled.
 #[cfg(feature = "plotting")]
 use gnuplot::{AxesCommon, Figure, Fix};
 
-use std::fmt;
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use core::fmt;
+#[cfg(feature = "std")]
 use std::fs::File;
-use std::io::{self, BufReader, Read};
+#[cfg(feature = "std")]
+use std::io::BufReader;
+#[cfg(feature = "std")]
 use std::path::Path;
 
 pub mod error;
+#[cfg(feature = "std")]
 use error::MnistError;
 
+pub mod parse;
+
+#[cfg(feature = "std")]
+pub mod builder;
+#[cfg(feature = "std")]
+pub use builder::{LabelEncoding, MnistBuilder, Normalize, Variant};
+
+#[cfg(feature = "download")]
+pub mod download;
+
+#[cfg(feature = "ndarray")]
+pub mod ndarray_support;
+
+#[cfg(feature = "png")]
+pub mod png_export;
+
 const IMAGE_WIDTH: usize = 28;
 const IMAGE_HEIGHT: usize = 28;
 pub const NPIXELS: usize = IMAGE_WIDTH * IMAGE_HEIGHT;
 
-/// Represents a single 28x28 MNIST image.
+/// Represents a single MNIST-family image, e.g. 28x28 for MNIST/EMNIST/Fashion-MNIST,
+/// or whatever `num_rows`/`num_cols` the IDX header declares.
 pub struct Image {
-    pixels: [u8; NPIXELS], // row-major order
+    pixels: Vec<u8>, // row-major order
+    width: usize,
+    height: usize,
 }
 
 impl fmt::Display for Image {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         const ASCII_GRADIENT: [char; 5] = [' ', '.', ':', '*', '@'];
 
-        for i in 0..NPIXELS {
-            let char_index = (self.pixels[i] as usize * ASCII_GRADIENT.len()) / 256;
+        for (i, &pixel) in self.pixels.iter().enumerate() {
+            let char_index = (pixel as usize * ASCII_GRADIENT.len()) / 256;
             write!(f, "{}", ASCII_GRADIENT[char_index])?;
-            if (i + 1) % IMAGE_WIDTH == 0 {
+            if (i + 1) % self.width == 0 {
                 writeln!(f)?;
             }
         }
@@ -40,13 +78,36 @@ impl fmt::Display for Image {
 }
 
 impl Image {
-    /// Creates an image from a normalized f64 array.
+    /// Creates a 28x28 image from a normalized f64 array.
+    ///
+    /// This is a fast path for the canonical MNIST geometry; use
+    /// [`Image::from_f64_slice`] for other dimensions (EMNIST, Fashion-MNIST, ...).
     pub fn from_f64_array(fa: &[f64; NPIXELS]) -> Image {
-        let mut pixels = [0u8; NPIXELS];
-        fa.iter()
-            .zip(pixels.iter_mut())
-            .for_each(|(f, p)| *p = (f * 255.0) as u8);
-        Image { pixels }
+        Image {
+            pixels: fa.iter().map(|f| (f * 255.0) as u8).collect(),
+            width: IMAGE_WIDTH,
+            height: IMAGE_HEIGHT,
+        }
+    }
+
+    /// Creates a `width x height` image from a normalized f64 slice.
+    pub fn from_f64_slice(width: usize, height: usize, fa: &[f64]) -> Image {
+        assert_eq!(fa.len(), width * height);
+        Image {
+            pixels: fa.iter().map(|f| (f * 255.0) as u8).collect(),
+            width,
+            height,
+        }
+    }
+
+    /// Returns the image width in pixels.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the image height in pixels.
+    pub fn height(&self) -> usize {
+        self.height
     }
 
     /// Returns the raw pixel data as a slice of bytes.
@@ -54,82 +115,92 @@ impl Image {
         &self.pixels
     }
 
-    /// Returns the pixel data as a 2D array.
-    pub fn as_2d_array(&self) -> &[[u8; IMAGE_WIDTH]; IMAGE_HEIGHT] {
-        // SAFETY: The memory layout of [u8; 784] is guaranteed to be identical
-        // to [[u8; 28]; 28], so this transmutation is safe.
-        unsafe { &*(self.pixels.as_ptr() as *const [[u8; IMAGE_WIDTH]; IMAGE_HEIGHT]) }
+    /// Returns the pixel data as rows of `width()` bytes each.
+    pub fn as_2d_array(&self) -> Vec<&[u8]> {
+        self.pixels.chunks(self.width).collect()
     }
 
     /// Returns the pixel data as a normalized f32 array (values 0.0 to 1.0).
-    pub fn as_f32_array(&self) -> [f32; IMAGE_WIDTH * IMAGE_HEIGHT] {
-        self.pixels.map(|p| p as f32 / 255.0)
+    ///
+    /// This is a fast path for the canonical 28x28 MNIST geometry; panics if the
+    /// image has different dimensions. Use [`Image::as_f32_vec`] for the general case.
+    pub fn as_f32_array(&self) -> [f32; NPIXELS] {
+        assert_eq!((self.width, self.height), (IMAGE_WIDTH, IMAGE_HEIGHT));
+        let mut out = [0f32; NPIXELS];
+        out.iter_mut()
+            .zip(&self.pixels)
+            .for_each(|(o, &p)| *o = p as f32 / 255.0);
+        out
     }
 
     /// Returns the pixel data as a normalized f64 array (values 0.0 to 1.0).
+    ///
+    /// This is a fast path for the canonical 28x28 MNIST geometry; panics if the
+    /// image has different dimensions. Use [`Image::as_f64_vec`] for the general case.
     pub fn as_f64_array(&self) -> [f64; NPIXELS] {
-        self.pixels.map(|p| p as f64 / 255.0)
+        assert_eq!((self.width, self.height), (IMAGE_WIDTH, IMAGE_HEIGHT));
+        let mut out = [0f64; NPIXELS];
+        out.iter_mut()
+            .zip(&self.pixels)
+            .for_each(|(o, &p)| *o = p as f64 / 255.0);
+        out
+    }
+
+    /// Returns the pixel data as a normalized f32 vector (values 0.0 to 1.0), for any geometry.
+    pub fn as_f32_vec(&self) -> Vec<f32> {
+        self.pixels.iter().map(|&p| p as f32 / 255.0).collect()
+    }
+
+    /// Returns the pixel data as a normalized f64 vector (values 0.0 to 1.0), for any geometry.
+    pub fn as_f64_vec(&self) -> Vec<f64> {
+        self.pixels.iter().map(|&p| p as f64 / 255.0).collect()
     }
 }
 
-fn read_u32(reader: &mut BufReader<File>) -> io::Result<u32> {
-    let mut buf = [0u8; 4];
-    reader.read_exact(&mut buf)?;
-    Ok(u32::from_be_bytes(buf))
+/// Reads MNIST labels from any [`parse::ByteReader`], e.g. a `BufReader<File>` or a
+/// `BufReader<impl Read>` wrapping a gzip decoder.
+#[cfg(feature = "std")]
+fn read_labels_from(reader: &mut impl parse::ByteReader) -> Result<Vec<u8>, MnistError> {
+    let header = parse::read_label_header(reader)?;
+    let mut labels = vec![0u8; header.num_items];
+    parse::read_labels_into(reader, &mut labels)?;
+    Ok(labels)
 }
 
 /// Reads the MNIST label file from the given path.
+#[cfg(feature = "std")]
 pub fn read_labels<P: AsRef<Path>>(path: P) -> Result<Vec<u8>, MnistError> {
     let file = File::open(path.as_ref())?;
     let mut reader = BufReader::new(file);
+    read_labels_from(&mut reader)
+}
 
-    let magic_number = read_u32(&mut reader)?;
-    if magic_number != 2049 {
-        return Err(MnistError::InvalidMagicNumber {
-            expected: 2049,
-            found: magic_number,
+/// Reads MNIST images from any [`parse::ByteReader`], e.g. a `BufReader<File>` or a
+/// `BufReader<impl Read>` wrapping a gzip decoder.
+#[cfg(feature = "std")]
+fn read_images_from(reader: &mut impl parse::ByteReader) -> Result<Vec<Image>, MnistError> {
+    let header = parse::read_image_header(reader)?;
+
+    let mut images = Vec::with_capacity(header.num_images);
+    for _ in 0..header.num_images {
+        let mut pixels = vec![0u8; header.num_rows * header.num_cols];
+        parse::read_images_into(reader, &mut pixels)?;
+        images.push(Image {
+            pixels,
+            width: header.num_cols,
+            height: header.num_rows,
         });
     }
 
-    let num_items = read_u32(&mut reader)?;
-    let mut labels = vec![0u8; num_items as usize];
-    reader.read_exact(&mut labels)?;
-
-    Ok(labels)
+    Ok(images)
 }
 
 /// Reads the MNIST image file from the given path.
+#[cfg(feature = "std")]
 pub fn read_images<P: AsRef<Path>>(path: P) -> Result<Vec<Image>, MnistError> {
     let file = File::open(path)?;
     let mut reader = BufReader::new(file);
-
-    let magic_number = read_u32(&mut reader)?;
-    if magic_number != 2051 {
-        return Err(MnistError::InvalidMagicNumber {
-            expected: 2051,
-            found: magic_number,
-        });
-    }
-
-    let num_images = read_u32(&mut reader)?;
-    let num_rows = read_u32(&mut reader)?;
-    let num_cols = read_u32(&mut reader)?;
-
-    if num_rows as usize != IMAGE_HEIGHT || num_cols as usize != IMAGE_WIDTH {
-        return Err(MnistError::InvalidImageDimensions {
-            expected: (IMAGE_WIDTH as u32, IMAGE_HEIGHT as u32),
-            found: (num_cols, num_rows),
-        });
-    }
-
-    let mut images = Vec::with_capacity(num_images as usize);
-    for _ in 0..num_images {
-        let mut pixels = [0u8; NPIXELS];
-        reader.read_exact(&mut pixels)?;
-        images.push(Image { pixels });
-    }
-
-    Ok(images)
+    read_images_from(&mut reader)
 }
 
 /// Plots an image using gnuplot.
@@ -143,7 +214,7 @@ pub fn plot(image: &Image, label: u8) {
     // Our data is top-to-bottom, so we reverse the rows.
     let z: Vec<u8> = image
         .pixels
-        .chunks(IMAGE_WIDTH)
+        .chunks(image.width)
         .rev()
         .flatten()
         .copied()
@@ -154,17 +225,24 @@ pub fn plot(image: &Image, label: u8) {
         .set_title(&format!("MNIST Label: {}", label), &[])
         .image(
             z.iter(),
-            IMAGE_WIDTH,
-            IMAGE_HEIGHT,
-            Some((0.0, 0.0, IMAGE_WIDTH as f64, IMAGE_HEIGHT as f64)),
+            image.width,
+            image.height,
+            Some((0.0, 0.0, image.width as f64, image.height as f64)),
             &[],
         );
 
     fg.show().unwrap();
 }
 
+/// The number of classes needed to cover `labels`, i.e. one more than the largest
+/// label value present (0 if `labels` is empty).
+#[cfg(feature = "std")]
+pub(crate) fn num_classes(labels: &[u8]) -> usize {
+    labels.iter().map(|&l| l as usize + 1).max().unwrap_or(0)
+}
+
 pub fn flatten_image(image: &[[u8; 28]; 28]) -> &[u8] {
-    unsafe { std::slice::from_raw_parts(image.as_ptr() as *const u8, 28 * 28) }
+    unsafe { core::slice::from_raw_parts(image.as_ptr() as *const u8, 28 * 28) }
 }
 
 pub fn unflatten_image(image: &[u8]) -> &[[u8; 28]; 28] {
@@ -172,32 +250,81 @@ pub fn unflatten_image(image: &[u8]) -> &[[u8; 28]; 28] {
     unsafe { &*(image.as_ptr() as *const [[u8; 28]; 28]) }
 }
 
+#[cfg(feature = "std")]
 pub struct Mnist {
     pub train_images: Vec<Image>,
     pub train_labels: Vec<u8>,
+    pub val_images: Vec<Image>,
+    pub val_labels: Vec<u8>,
     pub test_images: Vec<Image>,
     pub test_labels: Vec<u8>,
+    pub normalize: Normalize,
+    pub label_encoding: LabelEncoding,
+    /// One more than the largest label value across all three splits; the width
+    /// used by [`Mnist::encode_label`]'s [`LabelEncoding::OneHot`] vectors.
+    pub nclasses: usize,
 }
 
+#[cfg(feature = "std")]
 impl Mnist {
+    /// Loads the default digits dataset from `dir` with no validation split.
+    ///
+    /// Shorthand for `MnistBuilder::new(dir).build()`; use [`MnistBuilder`] directly
+    /// for normalization, label encoding, dataset variant, or a validation split.
     pub fn load<P: AsRef<Path>>(dir: P) -> Result<Self, MnistError> {
-        let dir = dir.as_ref();
-
-        let train_labels = read_labels(&dir.join("train-labels-idx1-ubyte"))?;
-        let train_images = read_images(&dir.join("train-images-idx3-ubyte"))?;
+        MnistBuilder::new(dir).build()
+    }
 
-        let test_labels = read_labels(&dir.join("t10k-labels-idx1-ubyte"))?;
-        let test_images = read_images(&dir.join("t10k-images-idx3-ubyte"))?;
+    /// Like [`Mnist::load`], but first fetches any of `filenames` missing from `dir`
+    /// from `base_url`, transparently gunzipping them.
+    ///
+    /// Pass [`download::DEFAULT_FILENAMES`] for the canonical MNIST digits files, or
+    /// a different list (together with a matching `base_url`) to load from a mirror
+    /// with a different layout.
+    ///
+    /// Only available if the "download" feature is enabled.
+    #[cfg(feature = "download")]
+    pub fn load_or_download<P: AsRef<Path>>(
+        dir: P,
+        base_url: &str,
+        filenames: &[(&str, &str)],
+    ) -> Result<Self, MnistError> {
+        download::download_missing(dir.as_ref(), base_url, filenames)?;
+        Self::load(dir)
+    }
 
-        assert!(train_labels.len()==train_labels.len());
-        assert!(test_labels.len()==test_images.len());
+    /// Applies this dataset's configured [`Normalize`] strategy to an image's pixels.
+    pub fn normalize_image(&self, image: &Image) -> Vec<f32> {
+        match self.normalize {
+            Normalize::Raw => image.as_u8_array().iter().map(|&p| p as f32).collect(),
+            Normalize::Unit => image.as_f32_vec(),
+            Normalize::Standardize => {
+                let values = image.as_f32_vec();
+                let mean = values.iter().sum::<f32>() / values.len() as f32;
+                let variance =
+                    values.iter().map(|v| (v - mean).powi(2)).sum::<f32>() / values.len() as f32;
+                let std_dev = variance.sqrt().max(f32::EPSILON);
+                values.iter().map(|v| (v - mean) / std_dev).collect()
+            }
+        }
+    }
 
-        Ok(Self {
-            train_images,
-            train_labels,
-            test_images,
-            test_labels,
-        })
+    /// Applies this dataset's configured [`LabelEncoding`] to a label.
+    ///
+    /// [`LabelEncoding::OneHot`] vectors are [`Mnist::nclasses`] wide; a `label` outside
+    /// that range (which should not happen for a label drawn from this dataset) yields
+    /// an all-zero vector rather than panicking.
+    pub fn encode_label(&self, label: u8) -> Vec<f32> {
+        match self.label_encoding {
+            LabelEncoding::Scalar => vec![label as f32],
+            LabelEncoding::OneHot => {
+                let mut one_hot = vec![0.0; self.nclasses];
+                if let Some(slot) = one_hot.get_mut(label as usize) {
+                    *slot = 1.0;
+                }
+                one_hot
+            }
+        }
     }
 }
 
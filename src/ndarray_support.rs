@@ -0,0 +1,119 @@
+//! Conversions from `Image`/`Mnist` into `ndarray` structures for training loops.
+//!
+//! Only compiled in if the "ndarray" feature is enabled.
+
+use crate::{Image, Mnist};
+use ndarray::{Array1, Array2, Array3};
+
+/// An `Mnist` dataset reshaped into `ndarray` arrays, ready for a training loop.
+pub struct NdarrayDataset {
+    /// Training images, shape `(n, height, width)`.
+    pub train_images: Array3<f32>,
+    /// Training labels, shape `(n,)`.
+    pub train_labels: Array1<u8>,
+    /// One-hot training labels, shape `(n, nclasses)`.
+    pub train_labels_one_hot: Array2<f32>,
+    /// Validation images carved off by [`crate::MnistBuilder::validation_split`], shape `(n, height, width)`.
+    pub val_images: Array3<f32>,
+    /// Validation labels, shape `(n,)`.
+    pub val_labels: Array1<u8>,
+    /// One-hot validation labels, shape `(n, nclasses)`.
+    pub val_labels_one_hot: Array2<f32>,
+    /// Test images, shape `(n, height, width)`.
+    pub test_images: Array3<f32>,
+    /// Test labels, shape `(n,)`.
+    pub test_labels: Array1<u8>,
+    /// One-hot test labels, shape `(n, nclasses)`.
+    pub test_labels_one_hot: Array2<f32>,
+}
+
+impl Image {
+    /// Returns the pixel data as a normalized `Array2<f32>` of shape `(height, width)`.
+    pub fn to_array2(&self) -> Array2<f32> {
+        Array2::from_shape_vec((self.height(), self.width()), self.as_f32_vec())
+            .expect("pixel vec length matches (height, width)")
+    }
+}
+
+impl Mnist {
+    /// Converts the dataset into `ndarray` arrays suitable for batched training.
+    ///
+    /// When `normalize` is `true`, pixel values are scaled to `[0.0, 1.0]`
+    /// (via [`Image::as_f32_vec`]); otherwise they are the raw `u8` values cast to `f32`.
+    /// Assumes every image in a given split shares the same dimensions.
+    ///
+    /// The one-hot width is [`Mnist::nclasses`], so datasets with more than 10 classes
+    /// (e.g. EMNIST) are encoded without panicking.
+    pub fn to_ndarray(&self, normalize: bool) -> NdarrayDataset {
+        NdarrayDataset {
+            train_images: images_to_array3(&self.train_images, normalize),
+            train_labels: Array1::from_vec(self.train_labels.clone()),
+            train_labels_one_hot: labels_to_one_hot(&self.train_labels, self.nclasses),
+            val_images: images_to_array3(&self.val_images, normalize),
+            val_labels: Array1::from_vec(self.val_labels.clone()),
+            val_labels_one_hot: labels_to_one_hot(&self.val_labels, self.nclasses),
+            test_images: images_to_array3(&self.test_images, normalize),
+            test_labels: Array1::from_vec(self.test_labels.clone()),
+            test_labels_one_hot: labels_to_one_hot(&self.test_labels, self.nclasses),
+        }
+    }
+}
+
+fn images_to_array3(images: &[Image], normalize: bool) -> Array3<f32> {
+    let (height, width) = images
+        .first()
+        .map(|image| (image.height(), image.width()))
+        .unwrap_or((0, 0));
+
+    let mut data = Vec::with_capacity(images.len() * height * width);
+    for image in images {
+        if normalize {
+            data.extend(image.as_f32_vec());
+        } else {
+            data.extend(image.as_u8_array().iter().map(|&p| p as f32));
+        }
+    }
+    Array3::from_shape_vec((images.len(), height, width), data)
+        .expect("data length matches (n, height, width)")
+}
+
+fn labels_to_one_hot(labels: &[u8], nclasses: usize) -> Array2<f32> {
+    let mut one_hot = Array2::zeros((labels.len(), nclasses));
+    for (i, &label) in labels.iter().enumerate() {
+        one_hot[[i, label as usize]] = 1.0;
+    }
+    one_hot
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_hot_matches_requested_width() {
+        let one_hot = labels_to_one_hot(&[0, 2, 1], 4);
+        assert_eq!(one_hot.shape(), &[3, 4]);
+        assert_eq!(one_hot[[0, 0]], 1.0);
+        assert_eq!(one_hot[[1, 2]], 1.0);
+        assert_eq!(one_hot[[2, 1]], 1.0);
+        assert_eq!(one_hot[[0, 1]], 0.0);
+    }
+
+    #[test]
+    fn one_hot_supports_more_than_ten_classes() {
+        let one_hot = labels_to_one_hot(&[15], 16);
+        assert_eq!(one_hot.shape(), &[1, 16]);
+        assert_eq!(one_hot[[0, 15]], 1.0);
+    }
+
+    #[test]
+    fn images_to_array3_has_n_height_width_shape() {
+        let images = vec![
+            Image::from_f64_slice(2, 2, &[0.0, 1.0, 0.0, 1.0]),
+            Image::from_f64_slice(2, 2, &[1.0, 0.0, 1.0, 0.0]),
+        ];
+        let arr = images_to_array3(&images, true);
+        assert_eq!(arr.shape(), &[2, 2, 2]);
+        assert_eq!(arr[[0, 0, 1]], 1.0);
+    }
+}
@@ -0,0 +1,213 @@
+//! Core IDX parsing built on a minimal [`ByteReader`] trait, so the same parsing
+//! logic works over a file or socket (`std::io::Read`, the "std" feature) or over
+//! an in-memory buffer ([`SliceReader`]) with no heap allocation beyond whatever
+//! buffer the caller supplies — useful in embedded/WASM contexts where the dataset
+//! is memory-mapped or embedded as a static byte array.
+
+use crate::error::MnistError;
+#[cfg(feature = "std")]
+use std::io::Read;
+
+/// A minimal byte source: fills a buffer completely, or reports
+/// [`MnistError::UnexpectedEof`] if the source is exhausted first.
+pub trait ByteReader {
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), MnistError>;
+}
+
+/// An allocation-free byte cursor over an in-memory buffer.
+pub struct SliceReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> SliceReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+}
+
+impl ByteReader for SliceReader<'_> {
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), MnistError> {
+        if buf.len() > self.bytes.len() {
+            return Err(MnistError::UnexpectedEof {
+                expected: buf.len(),
+                found: self.bytes.len(),
+            });
+        }
+        let (head, tail) = self.bytes.split_at(buf.len());
+        buf.copy_from_slice(head);
+        self.bytes = tail;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: Read> ByteReader for R {
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), MnistError> {
+        let mut total = 0;
+        while total < buf.len() {
+            match self.read(&mut buf[total..]) {
+                Ok(0) => {
+                    return Err(MnistError::UnexpectedEof {
+                        expected: buf.len(),
+                        found: total,
+                    })
+                }
+                Ok(n) => total += n,
+                Err(ref e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(MnistError::Io(e)),
+            }
+        }
+        Ok(())
+    }
+}
+
+fn read_u32(reader: &mut impl ByteReader) -> Result<u32, MnistError> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_be_bytes(buf))
+}
+
+/// Parsed IDX image-file header: image count and per-image geometry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageHeader {
+    pub num_images: usize,
+    pub num_rows: usize,
+    pub num_cols: usize,
+}
+
+impl ImageHeader {
+    /// Total pixel bytes described by this header (`num_images * num_rows * num_cols`).
+    pub const fn pixel_bytes(&self) -> usize {
+        self.num_images * self.num_rows * self.num_cols
+    }
+}
+
+/// Parsed IDX label-file header: item count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LabelHeader {
+    pub num_items: usize,
+}
+
+/// Reads and validates the IDX image-file header, without allocating or reading pixels.
+pub fn read_image_header(reader: &mut impl ByteReader) -> Result<ImageHeader, MnistError> {
+    let magic_number = read_u32(reader)?;
+    if magic_number != 2051 {
+        return Err(MnistError::InvalidMagicNumber {
+            expected: 2051,
+            found: magic_number,
+        });
+    }
+    Ok(ImageHeader {
+        num_images: read_u32(reader)? as usize,
+        num_rows: read_u32(reader)? as usize,
+        num_cols: read_u32(reader)? as usize,
+    })
+}
+
+/// Reads and validates the IDX label-file header, without allocating or reading labels.
+pub fn read_label_header(reader: &mut impl ByteReader) -> Result<LabelHeader, MnistError> {
+    let magic_number = read_u32(reader)?;
+    if magic_number != 2049 {
+        return Err(MnistError::InvalidMagicNumber {
+            expected: 2049,
+            found: magic_number,
+        });
+    }
+    Ok(LabelHeader {
+        num_items: read_u32(reader)? as usize,
+    })
+}
+
+/// Decodes raw pixel bytes into a caller-supplied buffer after [`read_image_header`].
+/// Reads exactly `buf.len()` bytes: pass `header.pixel_bytes()` bytes to decode every
+/// image at once, or `num_rows * num_cols` bytes per call to decode one image at a time.
+pub fn read_images_into(reader: &mut impl ByteReader, buf: &mut [u8]) -> Result<(), MnistError> {
+    reader.read_exact(buf)
+}
+
+/// Decodes raw label bytes into a caller-supplied buffer after [`read_label_header`].
+/// `buf.len()` must equal `header.num_items`.
+pub fn read_labels_into(reader: &mut impl ByteReader, buf: &mut [u8]) -> Result<(), MnistError> {
+    reader.read_exact(buf)
+}
+
+/// Returns the number of bytes required to decode `num_images` images of the given
+/// `num_rows x num_cols` geometry, including the 16-byte IDX header (magic number,
+/// count, rows, cols).
+pub const fn required_bytes(num_images: usize, num_rows: usize, num_cols: usize) -> usize {
+    16 + num_images * num_rows * num_cols
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header_bytes(magic: u32, a: u32, b: u32, c: u32) -> Vec<u8> {
+        [magic, a, b, c].iter().flat_map(|n| n.to_be_bytes()).collect()
+    }
+
+    #[test]
+    fn reads_valid_image_header() {
+        let bytes = header_bytes(2051, 3, 28, 28);
+        let mut reader = SliceReader::new(&bytes);
+        let header = read_image_header(&mut reader).unwrap();
+        assert_eq!(header.num_images, 3);
+        assert_eq!(header.num_rows, 28);
+        assert_eq!(header.num_cols, 28);
+        assert_eq!(header.pixel_bytes(), 3 * 28 * 28);
+    }
+
+    #[test]
+    fn reads_valid_label_header() {
+        let bytes = header_bytes(2049, 3, 0, 0);
+        let mut reader = SliceReader::new(&bytes);
+        let header = read_label_header(&mut reader).unwrap();
+        assert_eq!(header.num_items, 3);
+    }
+
+    #[test]
+    fn rejects_wrong_magic_number() {
+        let bytes = header_bytes(2049, 3, 28, 28);
+        let mut reader = SliceReader::new(&bytes);
+        let err = read_image_header(&mut reader).unwrap_err();
+        assert!(matches!(
+            err,
+            MnistError::InvalidMagicNumber {
+                expected: 2051,
+                found: 2049
+            }
+        ));
+    }
+
+    #[test]
+    fn truncated_header_is_unexpected_eof() {
+        let bytes = header_bytes(2051, 3, 28, 28);
+        let mut reader = SliceReader::new(&bytes[..10]);
+        let err = read_image_header(&mut reader).unwrap_err();
+        assert!(matches!(
+            err,
+            MnistError::UnexpectedEof {
+                expected: 4,
+                found: 2
+            }
+        ));
+    }
+
+    #[test]
+    fn truncated_pixel_data_is_unexpected_eof() {
+        let mut bytes = header_bytes(2051, 1, 2, 2);
+        bytes.extend_from_slice(&[1, 2, 3]); // one byte short of 2*2
+
+        let mut reader = SliceReader::new(&bytes);
+        let header = read_image_header(&mut reader).unwrap();
+        let mut buf = vec![0u8; header.pixel_bytes()];
+        let err = read_images_into(&mut reader, &mut buf).unwrap_err();
+        assert!(matches!(
+            err,
+            MnistError::UnexpectedEof {
+                expected: 4,
+                found: 3
+            }
+        ));
+    }
+}
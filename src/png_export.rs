@@ -0,0 +1,132 @@
+//! PNG export for `Image`/`Mnist` via the `image` crate.
+//!
+//! Only compiled in if the "png" feature is enabled (also requires "std").
+
+use crate::error::MnistError;
+use crate::{Image, Mnist};
+use image::{GrayImage, RgbImage};
+use std::ops::Range;
+use std::path::Path;
+
+/// Which split to export via [`Mnist::export_png`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dataset {
+    Train,
+    Test,
+}
+
+impl Image {
+    /// Materializes this image as a grayscale `image::GrayImage`.
+    pub fn to_gray_image(&self) -> GrayImage {
+        GrayImage::from_raw(self.width() as u32, self.height() as u32, self.as_u8_array().to_vec())
+            .expect("pixel vec length matches width * height")
+    }
+
+    /// Materializes this image as an `RgbImage`, replicating each pixel across R/G/B.
+    pub fn to_rgb_image(&self) -> RgbImage {
+        let mut rgb = Vec::with_capacity(self.as_u8_array().len() * 3);
+        for &p in self.as_u8_array() {
+            rgb.extend([p, p, p]);
+        }
+        RgbImage::from_raw(self.width() as u32, self.height() as u32, rgb)
+            .expect("pixel vec length matches width * height * 3")
+    }
+
+    /// Writes this image to `path` as a grayscale PNG.
+    pub fn to_png<P: AsRef<Path>>(&self, path: P) -> Result<(), MnistError> {
+        self.to_gray_image()
+            .save(path.as_ref())
+            .map_err(|e| MnistError::Png(e.to_string()))
+    }
+}
+
+impl Mnist {
+    /// Writes each image in `range` from `dataset` to `dir` as `label_<label>_<n>.png`,
+    /// where `label` is the image's actual class and `n` is its index within that split.
+    pub fn export_png<P: AsRef<Path>>(
+        &self,
+        dir: P,
+        dataset: Dataset,
+        range: Range<usize>,
+    ) -> Result<(), MnistError> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+
+        let (images, labels) = match dataset {
+            Dataset::Train => (&self.train_images, &self.train_labels),
+            Dataset::Test => (&self.test_images, &self.test_labels),
+        };
+
+        if range.end > images.len() {
+            return Err(MnistError::RangeOutOfBounds {
+                len: images.len(),
+                end: range.end,
+            });
+        }
+
+        for i in range {
+            images[i].to_png(dir.join(format!("label_{}_{i}.png", labels[i])))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LabelEncoding, Normalize};
+    use std::fs;
+
+    fn sample_images() -> Vec<Image> {
+        vec![
+            Image::from_f64_slice(2, 2, &[0.0, 0.25, 0.5, 1.0]),
+            Image::from_f64_slice(2, 2, &[1.0, 0.75, 0.5, 0.0]),
+        ]
+    }
+
+    fn sample_mnist() -> Mnist {
+        Mnist {
+            train_images: sample_images(),
+            train_labels: vec![3, 7],
+            val_images: Vec::new(),
+            val_labels: Vec::new(),
+            test_images: sample_images(),
+            test_labels: vec![3, 7],
+            normalize: Normalize::default(),
+            label_encoding: LabelEncoding::default(),
+            nclasses: 10,
+        }
+    }
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("mnist-rs-png-test-{name}-{}", std::process::id()))
+    }
+
+    #[test]
+    fn export_png_names_files_by_real_label() {
+        let mnist = sample_mnist();
+        let dir = temp_dir("names");
+
+        mnist.export_png(&dir, Dataset::Train, 0..2).unwrap();
+
+        assert!(dir.join("label_3_0.png").exists());
+        assert!(dir.join("label_7_1.png").exists());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn export_png_rejects_out_of_range() {
+        let mnist = sample_mnist();
+        let dir = temp_dir("oob");
+
+        let err = mnist.export_png(&dir, Dataset::Train, 0..5).unwrap_err();
+        assert!(matches!(
+            err,
+            MnistError::RangeOutOfBounds { len: 2, end: 5 }
+        ));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}